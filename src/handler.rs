@@ -1,6 +1,16 @@
-use axum::{extract::Path, response::Html};
+use std::sync::Arc;
 
-use crate::{error::AppError, mdx_options, meta, parse_mdx, template};
+use axum::{
+    extract::{Path, State},
+    http::{Method, header},
+    response::{Html, IntoResponse, Redirect, Response},
+};
+
+use markdown::mdast::Node;
+
+use crate::{
+    error::AppError, highlight, mdx_options, meta, metrics, parse_mdx, store::PageStore, template,
+};
 
 /// Serve the index page at `/`, mapping to `pages/index.mdx`.
 ///
@@ -12,10 +22,17 @@ use crate::{error::AppError, mdx_options, meta, parse_mdx, template};
 ///
 /// Returns `AppError::NotFound` if `pages/index.mdx` does not exist.
 /// Returns `AppError::ParseError` if the file cannot be parsed.
-pub async fn serve_index() -> Result<Html<String>, AppError> {
+pub async fn serve_index(method: Method) -> Result<Html<String>, AppError> {
+    metrics::record_page_hit(method.as_str(), "/");
     serve_mdx_file("pages/index.mdx").await
 }
 
+/// Serve `GET /healthcheck`: a liveness probe that always returns `200 OK`
+/// from memory, with no file I/O.
+pub async fn serve_healthcheck() -> &'static str {
+    "OK"
+}
+
 /// Serve the blog post listing at `/blog`.
 ///
 /// Reads all `pages/blog/*/page.mdx` files, skips drafts, and renders a
@@ -29,15 +46,72 @@ pub async fn serve_index() -> Result<Html<String>, AppError> {
 ///
 /// Returns `AppError::NotFound` if the `pages/blog` directory cannot be read.
 /// Returns `AppError::ParseError` if any post's frontmatter is malformed.
-pub async fn serve_blog_index() -> Result<Html<String>, AppError> {
+pub async fn serve_blog_index(method: Method) -> Result<Html<String>, AppError> {
+    metrics::record_page_hit(method.as_str(), "/blog");
     let posts = meta::list_posts().await?;
     Ok(Html(template::render_post_list(&posts)))
 }
 
+/// Serve the `/tags` index: every distinct tag and how many published posts carry it.
+///
+/// The tag index is pre-built once at startup in `store::PageStore::build`, so this
+/// handler does no I/O.
+pub async fn serve_tags_index(State(store): State<Arc<PageStore>>) -> Html<String> {
+    Html(template::render_tag_index(&store.tag_counts()))
+}
+
+/// Serve `/tags/{tag}`: the subset of published posts carrying `tag`.
+///
+/// # Errors
+///
+/// Returns `AppError::NotFound` if no post carries `tag`.
+pub async fn serve_tag(
+    State(store): State<Arc<PageStore>>,
+    Path(tag): Path<String>,
+) -> Result<Html<String>, AppError> {
+    let posts = store
+        .tags
+        .get(&tag)
+        .ok_or_else(|| AppError::NotFound(format!("tag: {tag}")))?;
+    Ok(Html(template::render_tag_posts(&tag, posts)))
+}
+
+/// Serve the RSS 2.0 syndication feed at `/feed.xml`, pre-rendered at startup in
+/// `store::PageStore::build`.
+pub async fn serve_feed(State(store): State<Arc<PageStore>>) -> Response {
+    (
+        [(header::CONTENT_TYPE, "application/rss+xml; charset=utf-8")],
+        store.feed_xml.clone(),
+    )
+        .into_response()
+}
+
+/// Serve the JSON Feed 1.1 document at `/feed.json`, pre-rendered at startup in
+/// `store::PageStore::build`.
+pub async fn serve_json_feed(State(store): State<Arc<PageStore>>) -> Response {
+    (
+        [(header::CONTENT_TYPE, "application/feed+json; charset=utf-8")],
+        store.feed_json.clone(),
+    )
+        .into_response()
+}
+
+/// Serve the Atom 1.0 feed at `/atom.xml`, pre-rendered at startup in
+/// `store::PageStore::build`. Kept at its own path alongside the RSS 2.0 feed
+/// at `/feed.xml`, rather than the two fighting over one route.
+pub async fn serve_atom_feed(State(store): State<Arc<PageStore>>) -> Response {
+    (
+        [(header::CONTENT_TYPE, "application/atom+xml; charset=utf-8")],
+        store.feed_atom.clone(),
+    )
+        .into_response()
+}
+
 /// Serve a page at `/{*path}`.
 ///
-/// Tries `pages/{path}/page.mdx` first (blog-post layout), then falls back
-/// to `pages/{path}.mdx` (flat page layout).
+/// If `path` matches a post's declared `aliases`, redirects permanently to the
+/// canonical URL instead. Otherwise tries `pages/{path}/page.mdx` first
+/// (blog-post layout), then falls back to `pages/{path}.mdx` (flat page layout).
 ///
 /// # Arguments
 ///
@@ -45,13 +119,25 @@ pub async fn serve_blog_index() -> Result<Html<String>, AppError> {
 ///
 /// # Returns
 ///
-/// A full HTML page for the requested path.
+/// A `301 Moved Permanently` for an aliased path, otherwise a full HTML page.
 ///
 /// # Errors
 ///
 /// Returns `AppError::NotFound` if neither file exists.
 /// Returns `AppError::ParseError` if MDX parsing fails.
-pub async fn serve_page(Path(path): Path<String>) -> Result<Html<String>, AppError> {
+pub async fn serve_page(
+    method: Method,
+    State(store): State<Arc<PageStore>>,
+    Path(path): Path<String>,
+) -> Result<Response, AppError> {
+    // Recorded only once `path` is known to resolve to something real, below —
+    // this is a wildcard route, so recording eagerly would give every 404 and
+    // typo'd URL its own `page_hit_count` label, an unbounded cardinality blowup.
+    if let Some(canonical) = store.aliases.get(&path) {
+        metrics::record_page_hit(method.as_str(), &format!("/{canonical}"));
+        return Ok(Redirect::permanent(&format!("/{canonical}")).into_response());
+    }
+
     // Blog posts live at pages/{slug}/page.mdx; fall back to a flat MDX file.
     let nested = format!("pages/{path}/page.mdx");
     let flat = format!("pages/{path}.mdx");
@@ -63,7 +149,9 @@ pub async fn serve_page(Path(path): Path<String>) -> Result<Html<String>, AppErr
         flat
     };
 
-    serve_mdx_file(&file_path).await
+    let response = serve_mdx_file(&file_path).await?;
+    metrics::record_page_hit(method.as_str(), &format!("/{path}"));
+    Ok(response.into_response())
 }
 
 /// Read an MDX file, parse it, extract metadata, and return a full HTML page.
@@ -74,7 +162,10 @@ pub async fn serve_page(Path(path): Path<String>) -> Result<Html<String>, AppErr
 ///
 /// # Errors
 ///
-/// Returns `AppError::NotFound` if the file does not exist.
+/// Returns `AppError::NotFound` if the file does not exist, or if it's a draft
+/// or scheduled for a future `date` — the same gate `meta::list_posts` and
+/// `store::PageStore::build` apply, so a direct link can't leak an unpublished
+/// post ahead of its listing.
 /// Returns `AppError::ParseError` if MDX parsing or metadata extraction fails.
 async fn serve_mdx_file(file_path: &str) -> Result<Html<String>, AppError> {
     // Read the file asynchronously; treat I/O errors as not-found.
@@ -89,8 +180,140 @@ async fn serve_mdx_file(file_path: &str) -> Result<Html<String>, AppError> {
     let opts = mdx_options::default_mdx_compile_options();
     let ast = parse_mdx(&content).map_err(AppError::ParseError)?;
     let page_meta = meta::extract_meta(&ast, &content)?;
+    if !page_meta.published {
+        return Err(AppError::NotFound(file_path.to_owned()));
+    }
     let html = markdown::to_html_with_options(&content, &opts)
         .map_err(|e| AppError::ParseError(e.to_string()))?;
+    let (html, needs_mermaid) = render_mermaid_blocks(&html);
+
+    let mut code_blocks = Vec::new();
+    collect_code_blocks(&ast, &mut code_blocks);
+    let html = highlight_code_blocks(&html, &code_blocks);
+
+    let html = match &page_meta.toc {
+        Some(toc) => inject_heading_ids(&html, &toc.heading_ids),
+        None => html,
+    };
+
+    Ok(Html(template::render_page(&page_meta, &html, needs_mermaid)))
+}
+
+/// Inject a matching `id="..."` attribute into each `<h1>`-`<h6>` tag in `html`, in
+/// document order, using the ids `meta::build_toc` computed from the same AST.
+fn inject_heading_ids(html: &str, heading_ids: &[meta::HeadingId]) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut rest = html;
+
+    for heading in heading_ids {
+        let open = format!("<h{}>", heading.depth);
+        let Some(idx) = rest.find(&open) else {
+            break;
+        };
+        out.push_str(&rest[..idx]);
+        out.push_str(&format!(r#"<h{} id="{}">"#, heading.depth, heading.id));
+        rest = &rest[idx + open.len()..];
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/// Collect every fenced code block's `(lang, raw source)` from `ast`, in document
+/// order, excluding blocks already rendered as Mermaid diagrams.
+fn collect_code_blocks(node: &Node, out: &mut Vec<(Option<String>, String)>) {
+    if let Node::Code(c) = node {
+        if c.lang.as_deref() != Some("mermaid") {
+            out.push((c.lang.clone(), c.value.clone()));
+        }
+        return;
+    }
+    if let Some(children) = node.children() {
+        for child in children {
+            collect_code_blocks(child, out);
+        }
+    }
+}
+
+/// Replace each fenced code block's plain escaped markup with syntax-highlighted,
+/// class-based `<span>` markup, in document order.
+///
+/// Blocks with no declared language, or a language `syntect` doesn't recognise, are
+/// left as the existing escaped plain-text rendering — highlighting must run on the
+/// raw fence contents (`raw`), not the already-HTML-escaped text in `html`.
+fn highlight_code_blocks(html: &str, blocks: &[(Option<String>, String)]) -> String {
+    const CLOSE: &str = "</code></pre>";
+    let mut out = String::with_capacity(html.len());
+    let mut rest = html;
+
+    for (lang, raw) in blocks {
+        let Some(lang) = lang else { continue };
+        let open = format!(r#"<pre><code class="language-{lang}">"#);
+        let Some(start) = rest.find(&open) else {
+            continue;
+        };
+        let after_open = &rest[start + open.len()..];
+        let Some(end) = after_open.find(CLOSE) else {
+            continue;
+        };
+        let block_end = start + open.len() + end + CLOSE.len();
+
+        match highlight::highlight_code(raw, lang) {
+            Some(highlighted) => {
+                out.push_str(&rest[..start]);
+                out.push_str(&highlighted);
+            }
+            // Unknown language: keep the existing plain-text rendering as-is.
+            None => out.push_str(&rest[..block_end]),
+        }
+        rest = &rest[block_end..];
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/// The exact markup `to_html_with_options` emits around a ```` ```mermaid ```` fence.
+const MERMAID_OPEN: &str = "<pre><code class=\"language-mermaid\">";
+const MERMAID_CLOSE: &str = "</code></pre>";
+
+/// Rewrite every ```` ```mermaid ```` fenced block's rendered markup from a plain
+/// code block into `<pre class="mermaid">` so the Mermaid runtime can pick it up.
+///
+/// The diagram source is left exactly as `to_html_with_options` emitted it, which is
+/// already HTML-escaped via the renderer's own escaping — no double-escaping needed.
+///
+/// # Returns
+///
+/// The rewritten HTML, and whether any mermaid block was found (so the caller knows
+/// whether to load the Mermaid runtime at all).
+fn render_mermaid_blocks(html: &str) -> (String, bool) {
+    if !html.contains(MERMAID_OPEN) {
+        return (html.to_owned(), false);
+    }
+
+    let mut out = String::with_capacity(html.len());
+    let mut rest = html;
+    let mut found = false;
+
+    while let Some(start) = rest.find(MERMAID_OPEN) {
+        out.push_str(&rest[..start]);
+        let after_open = &rest[start + MERMAID_OPEN.len()..];
+        let Some(end) = after_open.find(MERMAID_CLOSE) else {
+            // Unterminated block (shouldn't happen for well-formed HTML) — bail out
+            // and leave the remainder untouched rather than risk corrupting it.
+            out.push_str(&rest[start..]);
+            return (out, found);
+        };
+
+        let diagram = &after_open[..end];
+        out.push_str(r#"<pre class="mermaid">"#);
+        out.push_str(diagram);
+        out.push_str("</pre>");
+        found = true;
+        rest = &after_open[end + MERMAID_CLOSE.len()..];
+    }
 
-    Ok(Html(template::render_page(&page_meta, &html)))
+    out.push_str(rest);
+    (out, found)
 }
@@ -0,0 +1,88 @@
+use std::sync::LazyLock;
+
+use syntect::highlighting::ThemeSet;
+use syntect::html::{ClassStyle, ClassedHTMLGenerator, css_for_theme_with_class_style};
+use syntect::parsing::SyntaxSet;
+use syntect::util::LinesWithEndings;
+
+/// Default syntax definitions, loaded once and shared across every request.
+static SYNTAX_SET: LazyLock<SyntaxSet> = LazyLock::new(SyntaxSet::load_defaults_newlines);
+
+/// Default theme set, loaded once; used only to derive the class-based CSS below.
+static THEME_SET: LazyLock<ThemeSet> = LazyLock::new(ThemeSet::load_defaults);
+
+/// Light/dark class-based highlight CSS, computed once from the bundled themes and
+/// scoped under `[data-theme]` so highlighting follows the existing theme toggle.
+pub static HIGHLIGHT_STYLES: LazyLock<String> = LazyLock::new(|| {
+    let light =
+        css_for_theme_with_class_style(&THEME_SET.themes["InspiredGitHub"], ClassStyle::Spaced)
+            .unwrap_or_default();
+    let dark_raw = css_for_theme_with_class_style(
+        &THEME_SET.themes["base16-ocean.dark"],
+        ClassStyle::Spaced,
+    )
+    .unwrap_or_default();
+    // Prefix each selector rather than wrapping the block in a nested rule —
+    // native CSS nesting isn't supported everywhere, and an engine that lacks
+    // it would silently drop the whole dark palette.
+    let dark = scope_css(&dark_raw, "[data-theme='dark']");
+    format!("{light}\n{dark}")
+});
+
+/// Rewrite every selector in `css` to be scoped under `scope` (e.g.
+/// `[data-theme='dark']`), without relying on CSS nesting support.
+///
+/// `css` is assumed to be a flat sequence of `selector(, selector)* { ... }`
+/// rules, which is what `css_for_theme_with_class_style` emits.
+fn scope_css(css: &str, scope: &str) -> String {
+    let mut out = String::with_capacity(css.len() + css.len() / 4);
+    for rule in css.split_inclusive('}') {
+        let Some(brace) = rule.find('{') else {
+            // Trailing whitespace after the last rule.
+            out.push_str(rule);
+            continue;
+        };
+        let (selectors, rest) = rule.split_at(brace);
+        let scoped = selectors
+            .split(',')
+            .map(|s| format!("{scope} {}", s.trim()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        out.push_str(&scoped);
+        out.push(' ');
+        out.push_str(rest);
+    }
+    out
+}
+
+/// Tokenize `code` as `lang` into class-based HTML `<span>` markup, so highlighting
+/// follows the `[data-theme]` toggle via CSS rather than baking in inline colours.
+///
+/// # Arguments
+///
+/// * `code` - Raw (un-escaped) fence contents to tokenize
+/// * `lang` - The fence's declared language, e.g. `"rust"`
+///
+/// # Returns
+///
+/// The full `<pre><code>...</code></pre>` block, ready to replace the fence's
+/// existing plain-text markup wholesale — `ClassedHTMLGenerator::finalize`
+/// only emits the inner `<span>` lines, so this wraps them back in the same
+/// `<pre><code class="language-{lang}">` shell the unhighlighted fallback
+/// uses, preserving whitespace/indentation and letting the theme CSS (which
+/// expects a containing block element) apply.
+///
+/// `None` when `lang` has no known syntax definition — callers should fall back to
+/// the existing escaped plain-text rendering in that case.
+pub fn highlight_code(code: &str, lang: &str) -> Option<String> {
+    let syntax = SYNTAX_SET.find_syntax_by_token(lang)?;
+    let mut generator =
+        ClassedHTMLGenerator::new_with_class_style(syntax, &SYNTAX_SET, ClassStyle::Spaced);
+    for line in LinesWithEndings::from(code) {
+        generator.parse_html_for_line_which_includes_newline(line).ok()?;
+    }
+    Some(format!(
+        r#"<pre><code class="language-{lang}">{}</code></pre>"#,
+        generator.finalize()
+    ))
+}
@@ -1,9 +1,13 @@
 mod constants;
 mod error;
 mod handler;
+mod highlight;
 mod mdx_options;
+mod meta;
+mod metrics;
 mod page;
 mod post;
+mod search;
 mod store;
 mod template;
 
@@ -52,17 +56,36 @@ pub(crate) fn parse_mdx(content: &str) -> Result<markdown::mdast::Node, String>
 /// the in-memory store — no file I/O occurs at request time.
 ///
 /// Routes:
-/// - `GET /`        → pre-rendered `pages/index.mdx`
-/// - `GET /blog`    → pre-rendered listing of all published blog posts
-/// - `GET /{*path}` → pre-rendered page matching the given URL key
+/// - `GET /`                  → pre-rendered `pages/index.mdx`
+/// - `GET /blog`               → pre-rendered listing of all published blog posts
+/// - `GET /feed.xml`           → RSS 2.0 syndication feed of all published posts
+/// - `GET /feed.json`          → JSON Feed 1.1 document of all published posts
+/// - `GET /atom.xml`           → Atom 1.0 syndication feed of all published posts
+/// - `GET /search-index.json` → BM25-ready inverted search index, cached after first build
+/// - `GET /tags`               → every tag and how many published posts carry it
+/// - `GET /tags/{tag}`         → posts carrying the given tag
+/// - `GET /healthcheck`       → liveness probe, always `200 OK` from memory
+/// - `GET /{*path}`           → pre-rendered page, or a 301 if `path` is a known alias
+///
+/// If `BLOG_OTLP_ENABLED` is set, page-handler hits are exported as an
+/// OpenTelemetry `page_hit_count` metric; otherwise metric recording is a no-op.
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
+    metrics::init_otlp();
+
     let store =
         Arc::new(store::PageStore::build().map_err(|e| anyhow::anyhow!("startup failed: {e}"))?);
 
     let app = Router::new()
         .route("/", get(handler::serve_index))
         .route("/blog", get(handler::serve_blog_index))
+        .route("/feed.xml", get(handler::serve_feed))
+        .route("/feed.json", get(handler::serve_json_feed))
+        .route("/atom.xml", get(handler::serve_atom_feed))
+        .route("/search-index.json", get(search::serve_search_index))
+        .route("/tags", get(handler::serve_tags_index))
+        .route("/tags/{tag}", get(handler::serve_tag))
+        .route("/healthcheck", get(handler::serve_healthcheck))
         .route("/{*path}", get(handler::serve_page))
         .with_state(store)
         .layer(TraceLayer::new_for_http());
@@ -5,6 +5,11 @@ pub fn default_mdx_compile_options() -> markdown::Options {
             constructs: markdown::Constructs {
                 frontmatter: true,
                 code_indented: true,
+                // `$a^2+b^2$` and `$$...$$` parse into dedicated math nodes instead of
+                // being swallowed by surrounding text, so KaTeX can render them from the
+                // AST rather than a naive (and dollar-sign-in-code-fence-breaking) regex.
+                math_text: true,
+                math_flow: true,
                 ..markdown::Constructs::mdx()
             },
             ..markdown::ParseOptions::mdx()
@@ -1,7 +1,10 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, FixedOffset};
 use markdown::mdast::Node;
 use serde::Deserialize;
 
-use crate::error::AppError;
+use crate::{error::AppError, post, template::escape_html};
 
 /// Metadata extracted from a page's frontmatter and body text.
 #[derive(Debug, Clone)]
@@ -9,6 +12,33 @@ pub struct PageMeta {
     pub title: String,
     /// Estimated reading time in minutes (minimum 1).
     pub read_time_mins: usize,
+    /// Present when the page opts into `toc: true` frontmatter.
+    pub toc: Option<Toc>,
+    /// Original publish date, parsed from the `date` frontmatter field.
+    pub date: Option<DateTime<FixedOffset>>,
+    /// Present only when `updated` frontmatter parses to a date strictly after
+    /// `date`, so callers can render a "last updated" line alongside it.
+    pub updated: Option<DateTime<FixedOffset>>,
+    /// Whether this page should be servable yet: not `draft`, and not dated in
+    /// the future. See `post::is_published`.
+    pub published: bool,
+}
+
+/// A single entry in a generated table of contents: a heading's nesting depth
+/// (1-6) and the slug id assigned to it, used to inject a matching `id`
+/// attribute into the corresponding rendered `<h1>`-`<h6>` tag.
+#[derive(Debug, Clone)]
+pub struct HeadingId {
+    pub depth: u8,
+    pub id: String,
+}
+
+/// A page's generated table of contents: nested `<ul>` markup linking to every
+/// heading, plus the heading ids it links to (so callers can anchor them).
+#[derive(Debug, Clone)]
+pub struct Toc {
+    pub html: String,
+    pub heading_ids: Vec<HeadingId>,
 }
 
 /// Summary data for a single blog post, used in the post listing.
@@ -17,8 +47,12 @@ pub struct PostListing {
     pub title: String,
     /// URL-safe directory name under `pages/blog/`.
     pub slug: String,
-    pub date: Option<String>,
+    pub date: Option<DateTime<FixedOffset>>,
+    /// Present only when `updated` frontmatter parses to a date strictly after `date`.
+    pub updated: Option<DateTime<FixedOffset>>,
     pub description: Option<String>,
+    /// Topic tags this post is filed under, for `/tags` browsing.
+    pub tags: Vec<String>,
 }
 
 /// All supported frontmatter fields.
@@ -28,10 +62,21 @@ pub struct PostListing {
 struct Frontmatter {
     title: String,
     date: Option<String>,
+    /// A later revision date. Shown as a "last updated" date when it parses
+    /// to a point strictly after `date`.
+    #[serde(default)]
+    updated: Option<String>,
     description: Option<String>,
     /// When `true`, the post is excluded from public listings.
     #[serde(default)]
     draft: Option<bool>,
+    /// When `true`, a table of contents generated from this page's headings is
+    /// rendered above its content.
+    #[serde(default)]
+    toc: Option<bool>,
+    /// Topic tags this post is filed under.
+    #[serde(default)]
+    tags: Vec<String>,
 }
 
 /// Return the raw YAML string from the first [`Node::Yaml`] child of `ast`.
@@ -67,11 +112,12 @@ fn yaml_from_ast(ast: &Node) -> &str {
 ///
 /// # Returns
 ///
-/// A [`PageMeta`] with the page title and read-time estimate.
+/// A [`PageMeta`] with the page title, read-time estimate, and publish/update dates.
 ///
 /// # Errors
 ///
-/// Returns [`AppError::ParseError`] if the YAML block cannot be deserialised.
+/// Returns [`AppError::ParseError`] if the YAML block cannot be deserialised, or
+/// if `date`/`updated` are present but not valid RFC 3339.
 pub fn extract_meta(ast: &Node, content: &str) -> Result<PageMeta, AppError> {
     let yaml = yaml_from_ast(ast);
     let fm: Frontmatter =
@@ -83,22 +129,147 @@ pub fn extract_meta(ast: &Node, content: &str) -> Result<PageMeta, AppError> {
     // Integer division: 400 words → 2 min; fewer than 200 words → 1 min (minimum).
     let read_time_mins = (word_count / 200).max(1);
 
+    let toc = matches!(fm.toc, Some(true)).then(|| build_toc(ast));
+
+    let date = post::parse_date(&fm.title, fm.date.as_deref())?;
+    let updated_raw = post::parse_date(&fm.title, fm.updated.as_deref())?;
+    let updated = post::gate_updated(date, updated_raw);
+    let published = post::is_published(date, fm.draft);
+
     Ok(PageMeta {
         title: fm.title,
         read_time_mins,
+        toc,
+        date,
+        updated,
+        published,
     })
 }
 
+/// Walk `ast` collecting every heading into a nested table-of-contents `<ul>`,
+/// assigning each heading a unique slug id for cross-linking with the rendered tag.
+fn build_toc(ast: &Node) -> Toc {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    let mut headings: Vec<(u8, String, String)> = Vec::new();
+    collect_headings(ast, &mut headings, &mut seen);
+
+    let heading_ids = headings
+        .iter()
+        .map(|(depth, _, id)| HeadingId {
+            depth: *depth,
+            id: id.clone(),
+        })
+        .collect();
+    let html = render_toc_list(&headings);
+
+    Toc { html, heading_ids }
+}
+
+/// Recursively collect every `Node::Heading` in document order as
+/// `(depth, heading text, slug id)`. Headings don't nest, so recursion stops
+/// at each one found.
+fn collect_headings(node: &Node, out: &mut Vec<(u8, String, String)>, seen: &mut HashMap<String, usize>) {
+    if let Node::Heading(h) = node {
+        let text = crate::post::body_text_from_ast(node);
+        let id = slugify(&text, seen);
+        out.push((h.depth, text, id));
+        return;
+    }
+    if let Some(children) = node.children() {
+        for child in children {
+            collect_headings(child, out, seen);
+        }
+    }
+}
+
+/// Slugify heading text for an anchor id: lowercase, collapse whitespace runs
+/// to single hyphens, and drop characters outside `[a-z0-9-]`. Collisions are
+/// disambiguated by appending `-1`, `-2`, ... via the `seen` counter.
+fn slugify(text: &str, seen: &mut HashMap<String, usize>) -> String {
+    let mut base = String::with_capacity(text.len());
+    let mut last_was_hyphen = true; // suppresses a leading hyphen
+    for ch in text.chars() {
+        if ch.is_whitespace() {
+            if !last_was_hyphen {
+                base.push('-');
+                last_was_hyphen = true;
+            }
+        } else {
+            let lower = ch.to_ascii_lowercase();
+            if lower.is_ascii_alphanumeric() || lower == '-' {
+                base.push(lower);
+                last_was_hyphen = lower == '-';
+            }
+        }
+    }
+    let base = base.trim_matches('-');
+    let base = if base.is_empty() { "section" } else { base };
+
+    let mut count = seen.get(base).copied().unwrap_or(0);
+    let mut slug = if count == 0 {
+        base.to_owned()
+    } else {
+        format!("{base}-{count}")
+    };
+    // A generated `base-n` can equal another heading's own slug; skip past it.
+    while count > 0 && seen.contains_key(&slug) {
+        count += 1;
+        slug = format!("{base}-{count}");
+    }
+    seen.insert(base.to_owned(), count + 1);
+    seen.entry(slug.clone()).or_insert(1);
+    slug
+}
+
+/// Render a flat, document-ordered heading list into a nested `<ul>` table of
+/// contents, opening and closing lists via a depth stack as nesting changes.
+fn render_toc_list(headings: &[(u8, String, String)]) -> String {
+    let mut html = String::new();
+    let mut open_depths: Vec<u8> = Vec::new();
+
+    for (depth, text, id) in headings {
+        match open_depths.last().copied() {
+            None => {
+                html.push_str("<ul>");
+                open_depths.push(*depth);
+            }
+            Some(top) if *depth > top => {
+                html.push_str("<ul>");
+                open_depths.push(*depth);
+            }
+            Some(top) if *depth < top => {
+                while open_depths.len() > 1 && *open_depths.last().unwrap() > *depth {
+                    html.push_str("</li></ul>");
+                    open_depths.pop();
+                }
+                html.push_str("</li>");
+                *open_depths.last_mut().unwrap() = *depth;
+            }
+            _ => html.push_str("</li>"),
+        }
+        let text = escape_html(text);
+        html.push_str(&format!(r#"<li><a href="#{id}">{text}</a>"#));
+    }
+
+    for _ in &open_depths {
+        html.push_str("</li></ul>");
+    }
+
+    html
+}
+
 /// List all published blog posts found under `pages/blog/*/page.mdx`.
 ///
-/// Posts with `draft: true` in frontmatter are excluded.
+/// A post is excluded if `draft: true`, or if its `date` is in the future
+/// (scheduled posts are held back until their publish time arrives).
 /// The returned list is sorted by `date` descending; posts without a date
 /// appear at the end.
 ///
 /// # Errors
 ///
 /// Returns [`AppError::NotFound`] if `pages/blog` cannot be read.
-/// Returns [`AppError::ParseError`] if any post's frontmatter is malformed.
+/// Returns [`AppError::ParseError`] if any post's frontmatter is malformed,
+/// including a `date` that isn't valid RFC 3339.
 pub async fn list_posts() -> Result<Vec<PostListing>, AppError> {
     let mut dir = tokio::fs::read_dir("pages/blog")
         .await
@@ -139,15 +310,20 @@ pub async fn list_posts() -> Result<Vec<PostListing>, AppError> {
         let fm: Frontmatter =
             serde_yaml::from_str(yaml).map_err(|e| AppError::ParseError(format!("{slug}: {e}")))?;
 
-        if fm.draft == Some(true) {
+        let date = post::parse_date(&slug, fm.date.as_deref())?;
+        let updated_raw = post::parse_date(&slug, fm.updated.as_deref())?;
+        let updated = post::gate_updated(date, updated_raw);
+        if !post::is_published(date, fm.draft) {
             continue;
         }
 
         posts.push(PostListing {
             title: fm.title,
             slug,
-            date: fm.date,
+            date,
+            updated,
             description: fm.description,
+            tags: fm.tags,
         });
     }
 
@@ -0,0 +1,56 @@
+use std::sync::LazyLock;
+
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::Counter;
+
+/// Counts requests served by a page handler, tagged by `method` and `path`.
+///
+/// Backed by the global OpenTelemetry meter provider, which is a no-op until
+/// [`init_otlp`] installs a real one — so `record_page_hit` is cheap to call
+/// unconditionally and ships no data unless metrics are enabled.
+static PAGE_HIT_COUNT: LazyLock<Counter<u64>> = LazyLock::new(|| {
+    opentelemetry::global::meter("blog")
+        .u64_counter("page_hit_count")
+        .with_description("Number of requests served by a page handler")
+        .build()
+});
+
+/// Record one hit to a page handler.
+pub(crate) fn record_page_hit(method: &str, path: &str) {
+    PAGE_HIT_COUNT.add(
+        1,
+        &[
+            KeyValue::new("method", method.to_owned()),
+            KeyValue::new("path", path.to_owned()),
+        ],
+    );
+}
+
+/// Install a real OTLP metrics exporter as the global meter provider when the
+/// `BLOG_OTLP_ENABLED` environment variable is set.
+///
+/// When unset (the default), the global meter provider stays the
+/// `opentelemetry` crate's built-in no-op implementation, so `record_page_hit`
+/// calls are effectively free and no metrics leave the process.
+pub fn init_otlp() {
+    if std::env::var_os("BLOG_OTLP_ENABLED").is_none() {
+        return;
+    }
+
+    let exporter = match opentelemetry_otlp::MetricExporter::builder()
+        .with_tonic()
+        .build()
+    {
+        Ok(exporter) => exporter,
+        Err(e) => {
+            eprintln!("BLOG_OTLP_ENABLED is set but the OTLP exporter failed to build: {e}");
+            return;
+        }
+    };
+
+    let provider = opentelemetry_sdk::metrics::SdkMeterProvider::builder()
+        .with_periodic_exporter(exporter)
+        .build();
+
+    opentelemetry::global::set_meter_provider(provider);
+}
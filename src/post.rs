@@ -1,3 +1,4 @@
+use chrono::{DateTime, FixedOffset, Utc};
 use markdown::mdast::Node;
 use serde::{Deserialize, Serialize};
 
@@ -9,6 +10,7 @@ pub struct PostListing {
     pub title: String,
     /// URL-safe directory name under `pages/blog/`.
     pub slug: String,
+    /// RFC 3339, normalized from the parsed frontmatter date.
     pub date: Option<String>,
     pub description: Option<String>,
     /// Plain-text body content stripped of all markup, used for client-side full-text search.
@@ -45,19 +47,65 @@ pub(crate) fn extract_listing(slug: &str, ast: &Node) -> Result<Option<PostListi
     let fm: Frontmatter =
         serde_yaml::from_str(yaml).map_err(|e| AppError::ParseError(format!("{slug}: {e}")))?;
 
-    if matches!(fm.draft, Some(true)) {
+    let date = parse_date(slug, fm.date.as_deref())?;
+    if !is_published(date, fm.draft) {
         return Ok(None);
     }
 
     Ok(Some(PostListing {
         title: fm.title,
         slug: slug.to_owned(),
-        date: fm.date,
+        date: date.map(|d| d.to_rfc3339()),
         description: fm.description,
         body: body_text_from_ast(ast),
     }))
 }
 
+/// Parse a frontmatter date string (e.g. `date` or `updated`) as RFC 3339.
+///
+/// `context` is a human-readable label (a slug or title) used to identify the
+/// offending post in the error message.
+///
+/// # Errors
+///
+/// Returns [`AppError::ParseError`] if `raw` is present but not valid RFC 3339.
+pub(crate) fn parse_date(
+    context: &str,
+    raw: Option<&str>,
+) -> Result<Option<DateTime<FixedOffset>>, AppError> {
+    raw.map(|d| {
+        DateTime::parse_from_rfc3339(d)
+            .map_err(|e| AppError::ParseError(format!("{context}: invalid date {d:?}: {e}")))
+    })
+    .transpose()
+}
+
+/// Whether a post should appear in public listings: not marked `draft`, and
+/// either dateless or dated at or before now (so future-dated posts are held
+/// back until their publish time arrives).
+pub(crate) fn is_published(date: Option<DateTime<FixedOffset>>, draft: Option<bool>) -> bool {
+    if draft == Some(true) {
+        return false;
+    }
+    match date {
+        Some(d) => d <= Utc::now(),
+        None => true,
+    }
+}
+
+/// Only treat a parsed `updated` frontmatter date as an actual revision when
+/// it's strictly after `date` — a stray or backdated `updated` value isn't a
+/// "last updated" stamp worth surfacing on the page, in feeds, or anywhere else.
+pub(crate) fn gate_updated(
+    date: Option<DateTime<FixedOffset>>,
+    updated: Option<DateTime<FixedOffset>>,
+) -> Option<DateTime<FixedOffset>> {
+    match (date, updated) {
+        (Some(d), Some(u)) if u > d => Some(u),
+        _ => None,
+    }
+}
+
 /// Walk an MDX AST and collect all visible text content into a single string.
 ///
 /// Text nodes from different blocks are separated by spaces. YAML frontmatter
@@ -84,8 +132,9 @@ fn collect_text(node: &Node, buf: &mut String) {
         // Inline and fenced code is also valuable for search (e.g. function names, commands).
         Node::InlineCode(ic) => push_text(buf, &ic.value),
         Node::Code(c) => push_text(buf, &c.value),
-        // Skip: YAML frontmatter (metadata, not body) and raw HTML nodes (markup noise).
-        Node::Yaml(_) | Node::Html(_) => {}
+        // Skip: YAML frontmatter (metadata, not body), raw HTML nodes (markup noise),
+        // and math nodes (raw TeX source, not prose — indexing it would pollute search).
+        Node::Yaml(_) | Node::Html(_) | Node::Math(_) | Node::InlineMath(_) => {}
         // All container nodes: recurse into children.
         _ => {
             if let Some(children) = node.children() {
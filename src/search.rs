@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use axum::Json;
+use serde::Serialize;
+
+use crate::{error::AppError, post};
+
+/// A single term's posting: which post it appears in, and how many times.
+#[derive(Debug, Clone, Serialize)]
+struct Posting {
+    slug: String,
+    term_frequency: usize,
+}
+
+/// Per-post metadata needed to render and link a search result.
+#[derive(Debug, Clone, Serialize)]
+struct DocMeta {
+    slug: String,
+    title: String,
+    date: Option<String>,
+}
+
+/// A prebuilt inverted index over all published posts, ready for client-side BM25
+/// scoring. `search.js` computes, per query term t present in a document,
+/// `idf(t) * (tf * (k1 + 1)) / (tf + k1 * (1 - b + b * dl/avgdl))` using this data,
+/// with `idf(t) = ln(1 + (N - df + 0.5)/(df + 0.5))`.
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchIndex {
+    /// term -> postings list
+    postings: HashMap<String, Vec<Posting>>,
+    /// slug -> document length in tokens
+    doc_lengths: HashMap<String, usize>,
+    avg_doc_len: f64,
+    doc_count: usize,
+    docs: Vec<DocMeta>,
+}
+
+/// Computed on first request and reused for every subsequent one.
+static SEARCH_INDEX: OnceLock<SearchIndex> = OnceLock::new();
+
+/// Tokenize `text`: lowercase, split on non-alphanumerics, drop tokens shorter than
+/// 3 characters (too short to be discriminating for search).
+fn tokenize(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| t.chars().count() >= 3)
+        .map(str::to_owned)
+        .collect()
+}
+
+/// Build the inverted index from every published post under `pages/blog`.
+///
+/// Walks the same directory `meta::list_posts` does, but needs each post's full AST
+/// to extract body text via `post::extract_listing`, so this re-parses rather than
+/// reusing `list_posts`, whose listings carry frontmatter fields only. Synchronous
+/// file I/O, so it's called via `spawn_blocking`; it runs on the first search
+/// request (concurrent first requests may each run it; the first result is kept).
+fn build_index() -> Result<SearchIndex, AppError> {
+    let dir = std::fs::read_dir("pages/blog").map_err(|e| AppError::NotFound(format!("pages/blog: {e}")))?;
+
+    let mut postings: HashMap<String, Vec<Posting>> = HashMap::new();
+    let mut doc_lengths = HashMap::new();
+    let mut docs = Vec::new();
+    let mut total_len = 0usize;
+
+    for entry in dir {
+        let entry = entry.map_err(|e| AppError::ParseError(e.to_string()))?;
+        if !entry
+            .file_type()
+            .map_err(|e| AppError::ParseError(e.to_string()))?
+            .is_dir()
+        {
+            continue;
+        }
+        let Some(slug) = entry.file_name().to_str().map(str::to_owned) else {
+            continue;
+        };
+        let Ok(content) = std::fs::read_to_string(entry.path().join("page.mdx")) else {
+            continue;
+        };
+
+        let ast = crate::parse_mdx(&content).map_err(AppError::ParseError)?;
+        let Some(listing) = post::extract_listing(&slug, &ast)? else {
+            continue; // draft, or dated in the future
+        };
+
+        let tokens = tokenize(&listing.body);
+        let doc_len = tokens.len();
+        total_len += doc_len;
+        doc_lengths.insert(slug.clone(), doc_len);
+
+        let mut term_counts: HashMap<String, usize> = HashMap::new();
+        for token in tokens {
+            *term_counts.entry(token).or_insert(0) += 1;
+        }
+        for (term, term_frequency) in term_counts {
+            postings
+                .entry(term)
+                .or_default()
+                .push(Posting { slug: slug.clone(), term_frequency });
+        }
+
+        docs.push(DocMeta { slug, title: listing.title, date: listing.date });
+    }
+
+    let doc_count = docs.len();
+    let avg_doc_len = if doc_count == 0 { 0.0 } else { total_len as f64 / doc_count as f64 };
+
+    Ok(SearchIndex { postings, doc_lengths, avg_doc_len, doc_count, docs })
+}
+
+/// Serve `GET /search-index.json`: the prebuilt inverted index, built on first
+/// request and cached in memory for every request after that.
+///
+/// # Errors
+///
+/// Returns `AppError::NotFound` if `pages/blog` cannot be read.
+/// Returns `AppError::ParseError` if any post's frontmatter or MDX is malformed.
+pub async fn serve_search_index() -> Result<Json<SearchIndex>, AppError> {
+    if let Some(index) = SEARCH_INDEX.get() {
+        return Ok(Json(index.clone()));
+    }
+
+    let index = tokio::task::spawn_blocking(build_index)
+        .await
+        .map_err(|e| AppError::ParseError(e.to_string()))??;
+    Ok(Json(SEARCH_INDEX.get_or_init(|| index).clone()))
+}
@@ -0,0 +1,142 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Deserialize;
+
+use crate::constants::SITE_URL;
+use crate::error::AppError;
+use crate::meta::PostListing;
+use crate::{post, template};
+
+/// Pre-rendered, request-time-immutable blog data, built once at startup so
+/// handlers that use it never touch the filesystem per request.
+pub struct PageStore {
+    /// All published posts, sorted date descending.
+    pub posts: Vec<PostListing>,
+    /// Tag name -> published posts carrying that tag, sorted date descending.
+    pub tags: BTreeMap<String, Vec<PostListing>>,
+    /// Pre-rendered RSS 2.0 feed body, served at `/feed.xml`.
+    pub feed_xml: String,
+    /// Pre-rendered JSON Feed 1.1 body, served at `/feed.json`.
+    pub feed_json: String,
+    /// Pre-rendered Atom 1.0 feed body, served at `/atom.xml`.
+    pub feed_atom: String,
+    /// Old URL (no leading `/`) -> canonical URL (no leading `/`), for posts that
+    /// declare `aliases` in frontmatter. Preserves inbound links after a rename.
+    pub aliases: HashMap<String, String>,
+}
+
+/// Frontmatter fields needed to index a post by tag.
+///
+/// Unknown keys in YAML are silently ignored by serde.
+#[derive(Debug, Deserialize)]
+struct Frontmatter {
+    title: String,
+    date: Option<String>,
+    /// A later revision date; see `meta::Frontmatter::updated`.
+    #[serde(default)]
+    updated: Option<String>,
+    description: Option<String>,
+    #[serde(default)]
+    draft: Option<bool>,
+    #[serde(default)]
+    tags: Vec<String>,
+    /// URLs this post was previously published at.
+    #[serde(default)]
+    aliases: Vec<String>,
+}
+
+impl PageStore {
+    /// Walk `pages/blog` once, grouping published posts by `tags` frontmatter and
+    /// pre-rendering the syndication feeds, so neither touches the filesystem again.
+    ///
+    /// A post is excluded via [`post::is_published`] if it's a draft or its `date`
+    /// is in the future; scheduled posts only appear once the server is restarted
+    /// after their publish time passes, since this store is built once at startup.
+    ///
+    /// Uses blocking `std::fs` rather than `tokio::fs`: this runs once at startup,
+    /// before the server accepts any requests, so there's nothing to avoid blocking.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::NotFound` if `pages/blog` cannot be read.
+    /// Returns `AppError::ParseError` if any post's frontmatter or MDX is malformed,
+    /// including a `date` that isn't valid RFC 3339.
+    pub fn build() -> Result<Self, AppError> {
+        let dir =
+            std::fs::read_dir("pages/blog").map_err(|e| AppError::NotFound(format!("pages/blog: {e}")))?;
+
+        let mut posts: Vec<PostListing> = Vec::new();
+        let mut aliases: HashMap<String, String> = HashMap::new();
+
+        for entry in dir {
+            let entry = entry.map_err(|e| AppError::ParseError(e.to_string()))?;
+            if !entry
+                .file_type()
+                .map_err(|e| AppError::ParseError(e.to_string()))?
+                .is_dir()
+            {
+                continue;
+            }
+
+            let Some(slug) = entry.file_name().to_str().map(str::to_owned) else {
+                continue;
+            };
+            let Ok(content) = std::fs::read_to_string(entry.path().join("page.mdx")) else {
+                continue;
+            };
+
+            let ast = crate::parse_mdx(&content).map_err(AppError::ParseError)?;
+            let yaml = crate::page::yaml_from_ast(&ast);
+            let fm: Frontmatter = serde_yaml::from_str(yaml)
+                .map_err(|e| AppError::ParseError(format!("{slug}: {e}")))?;
+
+            let date = post::parse_date(&slug, fm.date.as_deref())?;
+            let updated_raw = post::parse_date(&slug, fm.updated.as_deref())?;
+            let updated = post::gate_updated(date, updated_raw);
+            if !post::is_published(date, fm.draft) {
+                continue;
+            }
+
+            let canonical = format!("blog/{slug}");
+            for alias in &fm.aliases {
+                aliases.insert(alias.trim_start_matches('/').to_owned(), canonical.clone());
+            }
+
+            posts.push(PostListing {
+                title: fm.title,
+                slug,
+                date,
+                updated,
+                description: fm.description,
+                tags: fm.tags,
+            });
+        }
+
+        // Same descending-date sort as `meta::list_posts`; dateless posts sink to the
+        // bottom. Grouping by tag below preserves this order within each tag's list.
+        posts.sort_by(|a, b| match (&b.date, &a.date) {
+            (Some(bd), Some(ad)) => bd.cmp(ad),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        });
+
+        let mut tags: BTreeMap<String, Vec<PostListing>> = BTreeMap::new();
+        for post in &posts {
+            for tag in &post.tags {
+                tags.entry(tag.clone()).or_default().push(post.clone());
+            }
+        }
+
+        let feed_xml = template::render_feed(&posts, SITE_URL);
+        let feed_json = template::render_json_feed(&posts, SITE_URL);
+        let feed_atom = template::render_atom_feed(&posts, SITE_URL);
+
+        Ok(Self { posts, tags, feed_xml, feed_json, feed_atom, aliases })
+    }
+
+    /// Distinct tags and how many published posts carry each, alphabetically sorted.
+    pub fn tag_counts(&self) -> Vec<(&str, usize)> {
+        self.tags.iter().map(|(tag, posts)| (tag.as_str(), posts.len())).collect()
+    }
+}
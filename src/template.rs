@@ -1,3 +1,4 @@
+use crate::highlight::HIGHLIGHT_STYLES;
 use crate::meta::{PageMeta, PostListing};
 
 const LINKEDIN_URL: &str = "https://www.linkedin.com/in/alexanderreyeswainwright";
@@ -132,10 +133,34 @@ const THEME_SCRIPT: &str = r"
 }());
 ";
 
+/// Inline JavaScript that renders KaTeX math once the deferred KaTeX bundle
+/// has loaded. Runs on `DOMContentLoaded` rather than immediately, since this
+/// script tag (unlike the `defer`red KaTeX `<script>` above it) executes inline.
+///
+/// `math_text`/`math_flow` in `mdx_options` make the markdown parser consume
+/// the `$`/`$$` delimiters at the AST level, so the emitted HTML has none left
+/// for a delimiter-scanning auto-render pass to find. Instead this targets the
+/// `code.language-math` elements the renderer emits directly — `math-inline`
+/// for `$...$`, `math-display` for `$$...$$` (the latter additionally wrapped
+/// in a `<pre>`) — and calls `katex.render` on each in place.
+const KATEX_INIT_SCRIPT: &str = r"
+document.addEventListener('DOMContentLoaded', function () {
+  document.querySelectorAll('code.language-math').forEach(function (el) {
+    katex.render(el.textContent, el, {
+      throwOnError: false,
+      displayMode: el.classList.contains('math-display')
+    });
+  });
+});
+";
+
 /// Escape characters that carry special meaning in HTML.
 ///
 /// Used for all user-supplied strings placed in HTML text nodes or attributes.
-fn escape_html(s: &str) -> String {
+///
+/// `pub(crate)` so other rendering code (e.g. `meta`'s table-of-contents builder)
+/// can share it instead of re-implementing escaping.
+pub(crate) fn escape_html(s: &str) -> String {
     // Process in a single pass to avoid repeated allocations.
     let mut out = String::with_capacity(s.len());
     for c in s.chars() {
@@ -160,8 +185,19 @@ fn escape_html(s: &str) -> String {
 ///
 /// * `page_title`   - Text for the browser-tab `<title>` element (will be HTML-escaped)
 /// * `body_content` - Pre-rendered HTML fragment placed inside `<main>`
-fn html_shell(page_title: &str, body_content: &str) -> String {
+/// * `needs_mermaid` - Whether `body_content` contains a `<pre class="mermaid">` block;
+///   the Mermaid runtime is only loaded when a page actually has a diagram to render.
+fn html_shell(page_title: &str, body_content: &str, needs_mermaid: bool) -> String {
     let page_title = escape_html(page_title);
+    let highlight_styles = HIGHLIGHT_STYLES.as_str();
+    let mermaid_script = if needs_mermaid {
+        r#"<script type="module">
+    import mermaid from "https://cdn.jsdelivr.net/npm/mermaid@11/dist/mermaid.esm.min.mjs";
+    mermaid.initialize({ startOnLoad: true });
+  </script>"#
+    } else {
+        ""
+    };
     format!(
         r#"<!DOCTYPE html>
 <html lang="en" data-theme="light">
@@ -173,7 +209,9 @@ fn html_shell(page_title: &str, body_content: &str) -> String {
   <link rel="preconnect" href="https://fonts.gstatic.com" crossorigin />
   <link href="https://fonts.googleapis.com/css2?family=Plus+Jakarta+Sans:wght@400;600;700;800&display=swap" rel="stylesheet" />
   <link rel="stylesheet" href="https://cdn.jsdelivr.net/npm/@picocss/pico@2/css/pico.classless.min.css" />
-  <style>{STYLES}</style>
+  <link rel="stylesheet" href="https://cdn.jsdelivr.net/npm/katex@0.16/dist/katex.min.css" />
+  <style>{STYLES}
+{highlight_styles}</style>
 </head>
 <body>
   <header>
@@ -192,6 +230,9 @@ fn html_shell(page_title: &str, body_content: &str) -> String {
     <a href="{GITHUB_URL}" target="_blank" rel="noopener noreferrer">GitHub</a>
   </footer>
   <script>{THEME_SCRIPT}</script>
+  <script defer src="https://cdn.jsdelivr.net/npm/katex@0.16/dist/katex.min.js"></script>
+  <script>{KATEX_INIT_SCRIPT}</script>
+  {mermaid_script}
 </body>
 </html>"#
     )
@@ -207,35 +248,48 @@ fn html_shell(page_title: &str, body_content: &str) -> String {
 ///
 /// * `meta`         - Frontmatter-derived title and reading-time estimate
 /// * `html_content` - MDX-rendered HTML fragment (not escaped — already HTML)
-pub fn render_page(meta: &PageMeta, html_content: &str) -> String {
+/// * `needs_mermaid` - Whether `html_content` contains a Mermaid diagram block
+pub fn render_page(meta: &PageMeta, html_content: &str, needs_mermaid: bool) -> String {
     let title = escape_html(&meta.title);
     let mins = meta.read_time_mins;
+    let date_line = meta
+        .date
+        .map(|d| {
+            let published = format!("Published {}", d.format("%Y-%m-%d"));
+            let updated = meta
+                .updated
+                .map(|u| format!(" &middot; Updated {}", u.format("%Y-%m-%d")))
+                .unwrap_or_default();
+            format!(r#"<p class="post-meta">{published}{updated}</p>"#)
+        })
+        .unwrap_or_default();
+    let toc_html = meta
+        .toc
+        .as_ref()
+        .map(|toc| format!(r#"<nav class="toc">{}</nav>"#, toc.html))
+        .unwrap_or_default();
     let body_content = format!(
         r#"<h1 class="page-title">{title}</h1>
 <p class="read-time">{mins} min read</p>
+{date_line}
+{toc_html}
 <div class="content">{html_content}</div>"#
     );
-    html_shell(&meta.title, &body_content)
+    html_shell(&meta.title, &body_content, needs_mermaid)
 }
 
-/// Render a `<ul>` listing of blog posts inside the shared HTML shell.
-///
-/// Each item shows the post title (linked to `/blog/{slug}`), optional date,
-/// and optional description.
-///
-/// # Arguments
-///
-/// * `posts` - Slice of post summaries, typically pre-sorted by date descending
-pub fn render_post_list(posts: &[PostListing]) -> String {
-    let items: String = posts
+/// Render the `<li>` items shared by `/blog` and `/tags/{tag}`: each
+/// shows the post title (linked to `/blog/{slug}`), optional date, and optional
+/// description.
+fn post_list_items(posts: &[PostListing]) -> String {
+    posts
         .iter()
         .map(|post| {
             let title = escape_html(&post.title);
             let href = format!("/blog/{}", post.slug);
             let meta_line = post
                 .date
-                .as_deref()
-                .map(|d| format!(r#"<p class="post-meta">{d}</p>"#))
+                .map(|d| format!(r#"<p class="post-meta">{}</p>"#, d.format("%Y-%m-%d")))
                 .unwrap_or_default();
             let desc_line = post
                 .description
@@ -247,8 +301,213 @@ pub fn render_post_list(posts: &[PostListing]) -> String {
                 .unwrap_or_default();
             format!(r#"<li><a href="{href}">{title}</a>{meta_line}{desc_line}</li>"#)
         })
-        .collect();
+        .collect()
+}
 
+/// Render a `<ul>` listing of blog posts inside the shared HTML shell.
+///
+/// # Arguments
+///
+/// * `posts` - Slice of post summaries, typically pre-sorted by date descending
+pub fn render_post_list(posts: &[PostListing]) -> String {
+    let items = post_list_items(posts);
     let body_content = format!(r#"<h1>Posts</h1><ul class="post-list">{items}</ul>"#);
-    html_shell("Posts", &body_content)
+    html_shell("Posts", &body_content, false)
+}
+
+/// Render the `/tags` index: every distinct tag and how many published posts
+/// carry it, alphabetically sorted.
+///
+/// # Arguments
+///
+/// * `tag_counts` - `(tag, post count)` pairs, as produced by `store::PageStore::tag_counts`
+pub fn render_tag_index(tag_counts: &[(&str, usize)]) -> String {
+    let items: String = tag_counts
+        .iter()
+        .map(|(tag, count)| {
+            let tag = escape_html(tag);
+            format!(r#"<li><a href="/tags/{tag}">{tag}</a> ({count})</li>"#)
+        })
+        .collect();
+    let body_content = format!(r#"<h1>Tags</h1><ul class="post-list">{items}</ul>"#);
+    html_shell("Tags", &body_content, false)
+}
+
+/// Render the subset of posts carrying a single tag, at `/tags/{tag}`.
+///
+/// # Arguments
+///
+/// * `tag`   - The tag being browsed
+/// * `posts` - Posts carrying `tag`, pre-sorted by date descending
+pub fn render_tag_posts(tag: &str, posts: &[PostListing]) -> String {
+    let tag_escaped = escape_html(tag);
+    let items = post_list_items(posts);
+    let body_content =
+        format!(r#"<h1>Posts tagged &ldquo;{tag_escaped}&rdquo;</h1><ul class="post-list">{items}</ul>"#);
+    html_shell(&format!("Posts tagged {tag}"), &body_content, false)
+}
+
+/// Render an RSS 2.0 feed from the given posts.
+///
+/// All author-supplied text flows through `escape_html` since titles and
+/// descriptions come straight from post frontmatter.
+///
+/// RSS 2.0 has no standard element for a "last modified" timestamp, so an
+/// item's `updated` date (when present) is carried via the widely-supported
+/// `atom:updated` extension rather than overloading `pubDate`, which stays
+/// the original publish date.
+///
+/// # Arguments
+///
+/// * `posts`    - Post summaries, ideally pre-sorted by date descending
+/// * `site_url` - Absolute base URL the feed and its entries are served from
+pub fn render_feed(posts: &[PostListing], site_url: &str) -> String {
+    let items: String = posts
+        .iter()
+        .map(|post| {
+            let title = escape_html(&post.title);
+            let link = format!("{site_url}/blog/{}", post.slug);
+            // RSS 2.0 mandates RFC 822 date-times for <pubDate>, not RFC 3339.
+            let pub_date = post
+                .date
+                .map(|d| format!("<pubDate>{}</pubDate>", d.to_rfc2822()))
+                .unwrap_or_default();
+            let updated = post
+                .updated
+                .map(|u| format!("<atom:updated>{}</atom:updated>", u.to_rfc3339()))
+                .unwrap_or_default();
+            let description = post
+                .description
+                .as_deref()
+                .map(|d| format!("<description>{}</description>", escape_html(d)))
+                .unwrap_or_default();
+            format!(
+                r#"<item><title>{title}</title><link>{link}</link><guid>{link}</guid>{pub_date}{updated}{description}</item>"#
+            )
+        })
+        .collect();
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom">
+  <channel>
+    <title>Alexander Reyes Wainwright</title>
+    <link>{site_url}/</link>
+    <description>Posts from Alexander Reyes Wainwright</description>
+    {items}
+  </channel>
+</rss>"#
+    )
+}
+
+/// Render an Atom 1.0 feed from the given posts, served at `/atom.xml`
+/// separately from the RSS 2.0 feed at `/feed.xml` (see `render_feed`).
+///
+/// All author-supplied text flows through `escape_html` since titles and
+/// descriptions come straight from post frontmatter. Both `<updated>` and
+/// `<published>` are required by Atom, so a dateless post falls back to the
+/// Unix epoch rather than omitting them.
+///
+/// # Arguments
+///
+/// * `posts`    - Post summaries, ideally pre-sorted by date descending
+/// * `site_url` - Absolute base URL the feed and its entries are served from
+pub fn render_atom_feed(posts: &[PostListing], site_url: &str) -> String {
+    const EPOCH: &str = "1970-01-01T00:00:00+00:00";
+
+    let feed_updated = posts
+        .iter()
+        .filter_map(|p| p.date)
+        .max()
+        .map(|d| d.to_rfc3339())
+        .unwrap_or_else(|| EPOCH.to_owned());
+
+    let entries: String = posts
+        .iter()
+        .map(|post| {
+            let title = escape_html(&post.title);
+            let link = format!("{site_url}/blog/{}", post.slug);
+            let updated = post
+                .date
+                .map(|d| d.to_rfc3339())
+                .unwrap_or_else(|| EPOCH.to_owned());
+            let summary = post
+                .description
+                .as_deref()
+                .map(|d| format!("<summary>{}</summary>", escape_html(d)))
+                .unwrap_or_default();
+            format!(
+                r#"<entry><title>{title}</title><link href="{link}"/><id>{link}</id><updated>{updated}</updated><published>{updated}</published>{summary}</entry>"#
+            )
+        })
+        .collect();
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <title>Alexander Reyes Wainwright</title>
+  <link href="{site_url}/atom.xml" rel="self"/>
+  <link href="{site_url}/"/>
+  <id>{site_url}/</id>
+  <updated>{feed_updated}</updated>
+  <author><name>Alexander Reyes Wainwright</name></author>
+  {entries}
+</feed>"#
+    )
+}
+
+/// Render a JSON Feed 1.1 document from the given posts.
+///
+/// # Arguments
+///
+/// * `posts`    - Post summaries, ideally pre-sorted by date descending
+/// * `site_url` - Absolute base URL the feed and its entries are served from
+pub fn render_json_feed(posts: &[PostListing], site_url: &str) -> String {
+    #[derive(serde::Serialize)]
+    struct JsonFeedItem {
+        id: String,
+        url: String,
+        title: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        content_text: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        date_published: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        date_modified: Option<String>,
+    }
+
+    #[derive(serde::Serialize)]
+    struct JsonFeed {
+        version: &'static str,
+        title: &'static str,
+        home_page_url: String,
+        feed_url: String,
+        items: Vec<JsonFeedItem>,
+    }
+
+    let items = posts
+        .iter()
+        .map(|post| {
+            let link = format!("{site_url}/blog/{}", post.slug);
+            JsonFeedItem {
+                id: link.clone(),
+                url: link,
+                title: post.title.clone(),
+                content_text: post.description.clone(),
+                date_published: post.date.map(|d| d.to_rfc3339()),
+                date_modified: post.updated.map(|d| d.to_rfc3339()),
+            }
+        })
+        .collect();
+
+    let feed = JsonFeed {
+        version: "https://jsonfeed.org/version/1.1",
+        title: "Alexander Reyes Wainwright",
+        home_page_url: format!("{site_url}/"),
+        feed_url: format!("{site_url}/feed.json"),
+        items,
+    };
+
+    // Feed construction above guarantees valid UTF-8 field values; this can't fail.
+    serde_json::to_string(&feed).expect("JSON feed serialization is infallible")
 }